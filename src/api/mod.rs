@@ -0,0 +1,150 @@
+// Copyright 2020-2022 Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements the schema used for communicating both requests and
+//! responses between the NitroSecureModule and its driver, via CBOR-encoded
+//! messages.
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
+
+/// Operations that `NitroSecureModule` can perform via
+/// `driver::nsm_process_request`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum Request {
+    /// Request the module's version, digest algorithm and PCR capabilities.
+    DescribeNSM,
+
+    /// Request the attestation document covering the enclave's measurements.
+    Attestation {
+        /// Additional signed user data, as bytes.
+        user_data: Option<ByteBuf>,
+        /// Nonce included to demonstrate the freshness of the document, as bytes.
+        nonce: Option<ByteBuf>,
+        /// Public key used to encrypt the attestation document, as bytes.
+        public_key: Option<ByteBuf>,
+    },
+
+    /// Request a chunk of entropy from the NSM's random number generator.
+    GetRandom,
+
+    /// Request to extend a PCR with the given data.
+    ExtendPCR {
+        /// Index of the PCR to extend.
+        index: u16,
+        /// Data to extend the PCR with.
+        data: Vec<u8>,
+    },
+
+    /// Request to describe the state of a PCR.
+    DescribePCR {
+        /// Index of the PCR to describe.
+        index: u16,
+    },
+
+    /// Request to lock a PCR, making it read-only for the rest of the
+    /// enclave's lifetime.
+    LockPCR {
+        /// Index of the PCR to lock.
+        index: u16,
+    },
+
+    /// Request to lock a range of PCRs, making them read-only for the rest
+    /// of the enclave's lifetime.
+    LockPCRs {
+        /// Number of PCRs, starting at index 0, to lock.
+        range: u16,
+    },
+}
+
+/// Responses received from the `NitroSecureModule` driver.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum Response {
+    /// The module's version, digest algorithm and PCR capabilities.
+    DescribeNSM {
+        /// Breaking API changes.
+        version_major: u16,
+        /// Non-breaking API changes.
+        version_minor: u16,
+        /// Patch changes that do not affect the API.
+        version_patch: u16,
+        /// Algorithm used by the module to extend and describe PCRs.
+        digest: Digest,
+        /// Number of PCRs the module exposes.
+        max_pcrs: u16,
+        /// Indices of the PCRs that have been locked (made read-only).
+        locked_pcrs: BTreeSet<u16>,
+    },
+
+    /// The attestation document, in COSE_Sign1 format.
+    Attestation {
+        /// The CBOR-encoded and signed attestation document.
+        document: Vec<u8>,
+    },
+
+    /// Random bytes generated by the NSM's entropy source.
+    GetRandom {
+        /// The random bytes.
+        random: Vec<u8>,
+    },
+
+    /// The new digest of the PCR after being extended.
+    ExtendPCR {
+        /// The current data (digest) of the PCR.
+        data: Vec<u8>,
+    },
+
+    /// The current state of a PCR.
+    DescribePCR {
+        /// Whether the PCR is locked (read-only).
+        lock: bool,
+        /// The current data (digest) of the PCR.
+        data: Vec<u8>,
+    },
+
+    /// Confirmation that the PCR has been locked.
+    LockPCR,
+
+    /// Confirmation that the PCRs have been locked.
+    LockPCRs,
+
+    /// An error occurred while processing the request.
+    Error(ErrorCode),
+}
+
+/// Error codes returned by the NSM driver when a request could not be
+/// satisfied.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    /// No errors.
+    Success,
+    /// The request has invalid parameters.
+    InvalidArgument,
+    /// The request refers to an invalid index (e.g. a PCR index that is out of range).
+    InvalidIndex,
+    /// The response does not correspond to the request.
+    InvalidResponse,
+    /// The index refers to a read-only (locked) PCR.
+    ReadOnlyIndex,
+    /// The requested operation is not valid.
+    InvalidOperation,
+    /// The provided buffer is too small for the response.
+    BufferTooSmall,
+    /// The input provided is too large.
+    InputTooLarge,
+    /// An internal error occurred while processing the request.
+    InternalError,
+}
+
+/// Digest algorithm used by the NSM to extend and describe PCRs.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Digest {
+    /// SHA-256.
+    SHA256,
+    /// SHA-384.
+    SHA384,
+    /// SHA-512.
+    SHA512,
+}
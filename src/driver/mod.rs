@@ -16,7 +16,6 @@ use crate::api::{Request, Response};
 use libc::ioctl;
 use nix::request_code_readwrite;
 use nix::unistd::close;
-use std::io::{IoSlice, IoSliceMut};
 
 use std::fs::OpenOptions;
 use std::mem;
@@ -24,7 +23,12 @@ use std::os::unix::io::{IntoRawFd, RawFd};
 
 const DEV_FILE: &str = "/dev/nsm";
 const NSM_IOCTL_MAGIC: u8 = 0x0A;
-const NSM_RESPONSE_MAX_SIZE: usize = 0x3000;
+
+/// Initial heap capacity reserved for a response, in bytes.
+const NSM_RESPONSE_MIN_SIZE: usize = 0x3000;
+/// Capacity past which `nsm_process_request` gives up growing the response
+/// buffer and reports the truncation instead of retrying again.
+const NSM_RESPONSE_MAX_SIZE: usize = 0x10000;
 
 #[derive(Debug)]
 pub enum Error {
@@ -46,13 +50,44 @@ impl From<serde_cbor::Error> for Error {
 
 type Result<T> = std::result::Result<T, Error>;
 
-/// NSM message structure to be used with `ioctl()`.
+/// An iovec as defined by the mainline (in-tree) Linux NSM driver's uapi
+/// (`drivers/misc/nsm.c`): an explicit 64-bit address/length pair, rather
+/// than the platform `iovec`, whose pointer and `size_t` length are 32-bit
+/// on 32-bit targets and would therefore diverge from the kernel's layout.
+#[repr(C)]
+struct NsmIoVec {
+    /// Address of the buffer, as seen by the kernel.
+    addr: u64,
+    /// Length of the buffer in bytes; updated by the driver in place to the
+    /// number of bytes it actually wrote, for the response iovec.
+    len: u64,
+}
+
+impl NsmIoVec {
+    fn for_slice(data: &[u8]) -> Self {
+        NsmIoVec {
+            addr: data.as_ptr() as u64,
+            len: data.len() as u64,
+        }
+    }
+
+    fn for_slice_mut(data: &mut [u8]) -> Self {
+        NsmIoVec {
+            addr: data.as_mut_ptr() as u64,
+            len: data.len() as u64,
+        }
+    }
+}
+
+/// NSM message structure to be used with `ioctl()`, matching the mainline
+/// uapi exactly so the crate works against the in-tree kernel driver and
+/// not just the out-of-tree module.
 #[repr(C)]
-struct NsmMessage<'a> {
+struct NsmMessage {
     /// User-provided data for the request
-    pub request: IoSlice<'a>,
+    request: NsmIoVec,
     /// Response data provided by the NSM pipeline
-    pub response: IoSliceMut<'a>,
+    response: NsmIoVec,
 }
 
 /// Encode an NSM `Request` value into a vector.  
@@ -91,23 +126,55 @@ fn nsm_ioctl(fd: i32, message: &mut NsmMessage) -> Result<()> {
     }
 }
 
+/// Whether an ioctl error indicates the response buffer was too small to
+/// hold the driver's reply.
+fn is_response_truncated(err: &Error) -> bool {
+    matches!(err, Error::Io(io_err) if io_err.raw_os_error() == Some(libc::ENOSPC))
+}
+
 /// Create a message with input data and output capacity from a given
 /// request, then send it to the NSM driver via `ioctl()` and wait
-/// for the driver's response.  
-/// *Argument 1 (input)*: The descriptor to the NSM device file.  
-/// *Argument 2 (input)*: The NSM request.  
+/// for the driver's response.
+/// *Argument 1 (input)*: The descriptor to the NSM device file.
+/// *Argument 2 (input)*: The NSM request.
 /// *Returns*: The corresponding NSM response from the driver.
 pub fn nsm_process_request(fd: i32, request: Request) -> Result<Response> {
-    let cbor_request = nsm_encode_request_to_cbor(request)?;
+    nsm_process_request_with_capacity(fd, request, NSM_RESPONSE_MIN_SIZE)
+}
 
-    let mut cbor_response: [u8; NSM_RESPONSE_MAX_SIZE] = [0; NSM_RESPONSE_MAX_SIZE];
-    let mut message = NsmMessage {
-        request: IoSlice::new(&cbor_request),
-        response: IoSliceMut::new(&mut cbor_response),
-    };
-    let _ = nsm_ioctl(fd, &mut message)?;
+/// Same as `nsm_process_request`, but lets the caller pick the initial
+/// response buffer capacity instead of `NSM_RESPONSE_MIN_SIZE`, saving a
+/// retry round-trip when the expected response size is already known.
+/// *Argument 1 (input)*: The descriptor to the NSM device file.
+/// *Argument 2 (input)*: The NSM request.
+/// *Argument 3 (input)*: The initial response buffer capacity, in bytes.
+/// *Returns*: The corresponding NSM response from the driver.
+pub fn nsm_process_request_with_capacity(
+    fd: i32,
+    request: Request,
+    capacity_hint: usize,
+) -> Result<Response> {
+    let cbor_request = nsm_encode_request_to_cbor(request)?;
 
-    Ok(nsm_decode_response_from_cbor(&message.response)?)
+    let mut capacity = capacity_hint.max(NSM_RESPONSE_MIN_SIZE);
+    loop {
+        let mut cbor_response = vec![0u8; capacity];
+        let mut message = NsmMessage {
+            request: NsmIoVec::for_slice(&cbor_request),
+            response: NsmIoVec::for_slice_mut(&mut cbor_response),
+        };
+
+        match nsm_ioctl(fd, &mut message) {
+            Ok(()) => {
+                let written = (message.response.len as usize).min(capacity);
+                return Ok(nsm_decode_response_from_cbor(&cbor_response[..written])?);
+            }
+            Err(err) if is_response_truncated(&err) && capacity < NSM_RESPONSE_MAX_SIZE => {
+                capacity = (capacity * 2).min(NSM_RESPONSE_MAX_SIZE);
+            }
+            Err(err) => return Err(err),
+        }
+    }
 }
 
 /// NSM library initialization function.  
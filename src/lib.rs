@@ -11,16 +11,31 @@
 
 pub mod api;
 pub mod driver;
+pub mod rng;
 
+use std::collections::BTreeSet;
 use std::os::unix::io::RawFd;
 
 use api::Request;
 use driver::{nsm_exit, nsm_init, nsm_process_request};
+use serde_bytes::ByteBuf;
 
 pub struct NitroSecureModule {
     fd: RawFd,
 }
 
+/// The module's version, digest algorithm and PCR capabilities, as returned
+/// by `NitroSecureModule::describe_nsm`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NsmDescription {
+    pub version_major: u16,
+    pub version_minor: u16,
+    pub version_patch: u16,
+    pub digest: api::Digest,
+    pub max_pcrs: u16,
+    pub locked_pcrs: BTreeSet<u16>,
+}
+
 #[derive(Debug)]
 pub enum Error {
     Io(std::io::Error),
@@ -29,6 +44,19 @@ pub enum Error {
     InvalidReponse,
 }
 
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "I/O error: {}", err),
+            Error::Cbor(err) => write!(f, "CBOR error: {}", err),
+            Error::NitroSecureModuleError(err_code) => write!(f, "NSM error: {:?}", err_code),
+            Error::InvalidReponse => write!(f, "invalid response from the NSM driver"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
 impl From<driver::Error> for Error {
     fn from(err: driver::Error) -> Self {
         match err {
@@ -46,6 +74,30 @@ impl NitroSecureModule {
         Ok(Self { fd })
     }
 
+    pub fn describe_nsm(&self) -> Result<NsmDescription> {
+        let request = Request::DescribeNSM;
+        let response = nsm_process_request(self.fd, request)?;
+        match response {
+            api::Response::DescribeNSM {
+                version_major,
+                version_minor,
+                version_patch,
+                digest,
+                max_pcrs,
+                locked_pcrs,
+            } => Ok(NsmDescription {
+                version_major,
+                version_minor,
+                version_patch,
+                digest,
+                max_pcrs,
+                locked_pcrs,
+            }),
+            api::Response::Error(err_code) => Err(Error::NitroSecureModuleError(err_code)),
+            _ => Err(Error::InvalidReponse),
+        }
+    }
+
     pub fn get_random(&self) -> Result<Vec<u8>> {
         let request = Request::GetRandom;
         let response = nsm_process_request(self.fd, request)?;
@@ -55,6 +107,65 @@ impl NitroSecureModule {
             _ => Err(Error::InvalidReponse),
         }
     }
+
+    pub fn attest(
+        &self,
+        user_data: Option<Vec<u8>>,
+        nonce: Option<Vec<u8>>,
+        public_key: Option<Vec<u8>>,
+    ) -> Result<Vec<u8>> {
+        let request = Request::Attestation {
+            user_data: user_data.map(ByteBuf::from),
+            nonce: nonce.map(ByteBuf::from),
+            public_key: public_key.map(ByteBuf::from),
+        };
+        let response = nsm_process_request(self.fd, request)?;
+        match response {
+            api::Response::Attestation { document } => Ok(document),
+            api::Response::Error(err_code) => Err(Error::NitroSecureModuleError(err_code)),
+            _ => Err(Error::InvalidReponse),
+        }
+    }
+
+    pub fn describe_pcr(&self, index: u16) -> Result<(bool, Vec<u8>)> {
+        let request = Request::DescribePCR { index };
+        let response = nsm_process_request(self.fd, request)?;
+        match response {
+            api::Response::DescribePCR { lock, data } => Ok((lock, data)),
+            api::Response::Error(err_code) => Err(Error::NitroSecureModuleError(err_code)),
+            _ => Err(Error::InvalidReponse),
+        }
+    }
+
+    pub fn extend_pcr(&self, index: u16, data: Vec<u8>) -> Result<Vec<u8>> {
+        let request = Request::ExtendPCR { index, data };
+        let response = nsm_process_request(self.fd, request)?;
+        match response {
+            api::Response::ExtendPCR { data } => Ok(data),
+            api::Response::Error(err_code) => Err(Error::NitroSecureModuleError(err_code)),
+            _ => Err(Error::InvalidReponse),
+        }
+    }
+
+    pub fn lock_pcr(&self, index: u16) -> Result<()> {
+        let request = Request::LockPCR { index };
+        let response = nsm_process_request(self.fd, request)?;
+        match response {
+            api::Response::LockPCR => Ok(()),
+            api::Response::Error(err_code) => Err(Error::NitroSecureModuleError(err_code)),
+            _ => Err(Error::InvalidReponse),
+        }
+    }
+
+    pub fn lock_pcrs(&self, range: u16) -> Result<()> {
+        let request = Request::LockPCRs { range };
+        let response = nsm_process_request(self.fd, request)?;
+        match response {
+            api::Response::LockPCRs => Ok(()),
+            api::Response::Error(err_code) => Err(Error::NitroSecureModuleError(err_code)),
+            _ => Err(Error::InvalidReponse),
+        }
+    }
 }
 
 impl Drop for NitroSecureModule {
@@ -0,0 +1,78 @@
+// Copyright 2022 Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! `rand_core` adapter backed by the NSM entropy source.
+//!
+//! `NsmRng` buffers one `GetRandom` response at a time, refilling it from the
+//! driver as it is drained, so that callers can use the NSM as a drop-in
+//! `RngCore`/`CryptoRng` seed source without manually looping over
+//! `NitroSecureModule::get_random`.
+
+use rand_core::{CryptoRng, Error as RandError, RngCore};
+
+use crate::NitroSecureModule;
+
+/// A `rand_core::RngCore`/`CryptoRng` implementation backed by the NSM's
+/// entropy source.
+pub struct NsmRng {
+    nsm: NitroSecureModule,
+    buffer: Vec<u8>,
+    pos: usize,
+}
+
+impl NsmRng {
+    /// Wrap a `NitroSecureModule` in an `NsmRng`.
+    pub fn new(nsm: NitroSecureModule) -> Self {
+        Self {
+            nsm,
+            buffer: Vec::new(),
+            pos: 0,
+        }
+    }
+
+    fn refill(&mut self) -> Result<(), RandError> {
+        self.buffer = self.nsm.get_random().map_err(RandError::new)?;
+        self.pos = 0;
+        if self.buffer.is_empty() {
+            return Err(RandError::new("NSM returned an empty entropy chunk"));
+        }
+        Ok(())
+    }
+}
+
+impl RngCore for NsmRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut buf = [0u8; 4];
+        self.fill_bytes(&mut buf);
+        u32::from_le_bytes(buf)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut buf = [0u8; 8];
+        self.fill_bytes(&mut buf);
+        u64::from_le_bytes(buf)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.try_fill_bytes(dest)
+            .expect("failed to read entropy from the NSM driver")
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), RandError> {
+        let mut filled = 0;
+        while filled < dest.len() {
+            if self.pos >= self.buffer.len() {
+                self.refill()?;
+            }
+            let available = self.buffer.len() - self.pos;
+            let to_copy = available.min(dest.len() - filled);
+            dest[filled..filled + to_copy]
+                .copy_from_slice(&self.buffer[self.pos..self.pos + to_copy]);
+            self.pos += to_copy;
+            filled += to_copy;
+        }
+        Ok(())
+    }
+}
+
+impl CryptoRng for NsmRng {}